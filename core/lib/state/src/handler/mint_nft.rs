@@ -1,16 +1,22 @@
 use num::{BigUint, ToPrimitive, Zero};
+use std::collections::HashSet;
 use std::time::Instant;
 
 use zksync_types::{
-    operations::MintNFTOp,
-    tokens::NFT,
-    tx::{calculate_token_address, calculate_token_data, calculate_token_hash},
-    Account, AccountUpdate, AccountUpdates, Address, MintNFT, Nonce, PubKeyHash, TokenId, ZkSyncOp,
+    operations::{MintNFTBatchOp, MintNFTOp, PreSignedMintNFTOp},
+    tokens::{NFTAttribute, NFT},
+    tx::{
+        calculate_token_address, calculate_token_attributes_hash, calculate_token_data,
+        calculate_token_hash,
+    },
+    Account, AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, H256, MintNFT,
+    MintNFTBatch, Nonce, PreSignedMintNFT, PubKeyHash, TokenId, ZkSyncOp,
 };
 
 use zksync_crypto::params::{
-    max_processable_token, MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ADDRESS, NFT_STORAGE_ACCOUNT_ID,
-    NFT_TOKEN_ID,
+    max_processable_token, MAX_MINT_NFT_BATCH_SIZE, MAX_NFT_ATTRIBUTES, MAX_ROYALTY_BPS,
+    MIN_NFT_TOKEN_ID, NFT_MINT_AUTH_NONCE_ID, NFT_STORAGE_ACCOUNT_ADDRESS, NFT_STORAGE_ACCOUNT_ID,
+    NFT_TOKEN_ATTRIBUTES_ID_OFFSET, NFT_TOKEN_ID, NFT_TOKEN_ROYALTY_ID_OFFSET,
 };
 
 use crate::{
@@ -18,6 +24,31 @@ use crate::{
     state::{CollectedFee, OpSuccess, ZkSyncState},
 };
 
+// The attribute/royalty slots for a token are derived by offsetting its `token_id`, and they
+// share the same balance namespace on `NFT_STORAGE_ACCOUNT_ID` as every other token's `token_data`
+// and slots. As long as every real token_id stays below `NFT_TOKEN_ATTRIBUTES_ID_OFFSET` (checked
+// in `mint_nft_item`), the three ranges are:
+//   - real token ids:  [0, NFT_TOKEN_ATTRIBUTES_ID_OFFSET)
+//   - attribute slots: [NFT_TOKEN_ATTRIBUTES_ID_OFFSET, 2 * NFT_TOKEN_ATTRIBUTES_ID_OFFSET)
+//   - royalty slots:   [NFT_TOKEN_ROYALTY_ID_OFFSET, ...)
+// which are pairwise disjoint only if `NFT_TOKEN_ROYALTY_ID_OFFSET` is at least twice
+// `NFT_TOKEN_ATTRIBUTES_ID_OFFSET`. Asserted here, rather than left as an accident of values
+// defined outside this file, so a future change to either constant that breaks the ordering fails
+// to compile instead of silently corrupting a neighboring token's slot.
+const _: () = assert!(
+    NFT_TOKEN_ROYALTY_ID_OFFSET as u64 >= 2 * NFT_TOKEN_ATTRIBUTES_ID_OFFSET as u64
+);
+
+/// Where a token came from: its creator, the creator's serial id for it, and the block it was
+/// minted in. Looked up by `TokenId` via [`ZkSyncState::nft_provenance`] so explorers/wallets can
+/// answer "what is the origin of token T" without replaying mint history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NFTProvenance {
+    pub creator_id: AccountId,
+    pub serial_id: u32,
+    pub mint_block: BlockNumber,
+}
+
 impl TxHandler<MintNFT> for ZkSyncState {
     type Op = MintNFTOp;
     type OpError = MintNFTOpError;
@@ -31,6 +62,14 @@ impl TxHandler<MintNFT> for ZkSyncState {
             tx.recipient != Address::zero(),
             MintNFTOpError::RecipientAccountIncorrect
         );
+        invariant!(
+            tx.attributes.len() <= MAX_NFT_ATTRIBUTES,
+            MintNFTOpError::TooManyAttributes
+        );
+        invariant!(
+            tx.royalty_bps <= MAX_ROYALTY_BPS,
+            MintNFTOpError::InvalidRoyalty
+        );
         let creator = self
             .get_account(tx.creator_id)
             .ok_or(MintNFTOpError::CreatorAccountNotFound)?;
@@ -103,24 +142,493 @@ impl TxHandler<MintNFT> for ZkSyncState {
         ));
         self.insert_account(op.creator_account_id, creator_account.clone());
 
-        // Serial ID is a counter in a special balance for NFT_TOKEN, which shows how many nft were generated by this creator
+        let mint_nonce = nonce;
+        let (_token, item_updates) = self.mint_nft_item(
+            op.creator_account_id,
+            &mut creator_account,
+            mint_nonce,
+            op.recipient_account_id,
+            op.tx.content_hash,
+            op.tx.attributes.clone(),
+            op.tx.royalty_bps,
+        )?;
+        updates.extend(item_updates);
+
+        let fee = CollectedFee {
+            token: op.tx.fee_token,
+            amount: op.tx.fee.clone(),
+        };
+
+        metrics::histogram!("state.mint_nft", start.elapsed());
+        Ok((Some(fee), updates))
+    }
+}
+/// A pre-signed mint is authorized off-chain by the creator but submitted (and paid for) by a
+/// different account. This is useful for gasless drops where the creator never holds an L2
+/// balance: a relayer collects the creator's signature and fronts the fee.
+impl TxHandler<PreSignedMintNFT> for ZkSyncState {
+    type Op = PreSignedMintNFTOp;
+    type OpError = MintNFTOpError;
+
+    fn create_op(&self, tx: PreSignedMintNFT) -> Result<Self::Op, Self::OpError> {
+        invariant!(
+            tx.fee_token <= max_processable_token(),
+            MintNFTOpError::InvalidTokenId
+        );
+        invariant!(
+            tx.attributes.len() <= MAX_NFT_ATTRIBUTES,
+            MintNFTOpError::TooManyAttributes
+        );
+        // The creator only ever signs off on a ceiling; the submitter picks the actual fee within
+        // it, since the submitter is the one paying it.
+        invariant!(tx.fee <= tx.max_fee, MintNFTOpError::FeeExceedsMaxFee);
+        let creator = self
+            .get_account(tx.creator_id)
+            .ok_or(MintNFTOpError::CreatorAccountNotFound)?;
+        invariant!(
+            creator.pub_key_hash != PubKeyHash::default(),
+            MintNFTOpError::CreatorAccountIsLocked
+        );
+
+        if let Some((pub_key_hash, _)) = tx.verify_signature() {
+            if pub_key_hash != creator.pub_key_hash {
+                return Err(MintNFTOpError::InvalidSignature);
+            }
+        }
+
+        invariant!(
+            self.block_number <= tx.valid_until,
+            MintNFTOpError::AuthorizationExpired
+        );
+
+        let mint_auth_nonce = creator.get_balance(NFT_MINT_AUTH_NONCE_ID);
+        invariant!(
+            mint_auth_nonce == BigUint::from(tx.mint_auth_nonce.0),
+            MintNFTOpError::AuthorizationNonceMismatch
+        );
+
+        let (payer_account_id, payer_account) = self
+            .get_account_by_address(&tx.submitter_address)
+            .ok_or(MintNFTOpError::SubmitterAccountNotFound)?;
+
+        // The creator's signature never covers `submitter_address` (it's filled in by whoever is
+        // relaying the mint, not the creator), so without this check anyone holding a copy of a
+        // pre-signed authorization could point `submitter_address` at an unrelated victim account
+        // and have it pay the fee and advance its nonce with zero consent from that victim. The
+        // submitter must independently sign over their own address to prove they agreed to pay.
+        let submitter_pub_key_hash = tx
+            .verify_submitter_signature()
+            .ok_or(MintNFTOpError::InvalidSubmitterSignature)?;
+        invariant!(
+            submitter_pub_key_hash == payer_account.pub_key_hash,
+            MintNFTOpError::InvalidSubmitterSignature
+        );
+
+        // An open authorization (recipient left as the zero address) is bound to whoever submits it.
+        let recipient_address = if tx.recipient == Address::zero() {
+            tx.submitter_address
+        } else {
+            tx.recipient
+        };
+        let recipient_account_id = self
+            .get_account_by_address(&recipient_address)
+            .ok_or(MintNFTOpError::RecipientAccountNotFound)?
+            .0;
+
+        let op = PreSignedMintNFTOp {
+            creator_account_id: tx.creator_id,
+            recipient_account_id,
+            payer_account_id,
+            tx,
+        };
+
+        Ok(op)
+    }
+
+    fn apply_tx(&mut self, tx: PreSignedMintNFT) -> Result<OpSuccess, Self::OpError> {
+        let op = self.create_op(tx)?;
+
+        let (fee, updates) = <Self as TxHandler<PreSignedMintNFT>>::apply_op(self, &op)?;
+        let result = OpSuccess {
+            fee,
+            updates,
+            executed_op: ZkSyncOp::PreSignedMintNFTOp(Box::new(op)),
+        };
+
+        Ok(result)
+    }
+
+    fn apply_op(
+        &mut self,
+        op: &Self::Op,
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), Self::OpError> {
+        let start = Instant::now();
+        let mut updates = Vec::new();
+
+        // Unlike a regular mint, the fee is paid by the submitter, not the creator: the creator
+        // may never hold an L2 balance at all.
+        invariant!(op.tx.fee <= op.tx.max_fee, MintNFTOpError::FeeExceedsMaxFee);
+        let mut payer_account = self
+            .get_account(op.payer_account_id)
+            .ok_or(MintNFTOpError::SubmitterAccountNotFound)?;
+        // Re-checked here for the same reason as the mint-auth nonce below: if `apply_op` is ever
+        // invoked directly against an already-built op, the victim-drain this guards against must
+        // still be caught at the point the fee is actually taken, not only when the op was built.
+        let submitter_pub_key_hash = op
+            .tx
+            .verify_submitter_signature()
+            .ok_or(MintNFTOpError::InvalidSubmitterSignature)?;
+        invariant!(
+            submitter_pub_key_hash == payer_account.pub_key_hash,
+            MintNFTOpError::InvalidSubmitterSignature
+        );
+        let old_balance = payer_account.get_balance(op.tx.fee_token);
+        invariant!(
+            old_balance >= op.tx.fee,
+            MintNFTOpError::InsufficientBalance
+        );
+        payer_account.sub_balance(op.tx.fee_token, &op.tx.fee);
+        let new_balance = payer_account.get_balance(op.tx.fee_token);
+        let old_nonce = payer_account.nonce;
+        *payer_account.nonce += 1;
+        updates.push((
+            op.payer_account_id,
+            AccountUpdate::UpdateBalance {
+                balance_update: (op.tx.fee_token, old_balance, new_balance),
+                old_nonce,
+                new_nonce: payer_account.nonce,
+            },
+        ));
+        self.insert_account(op.payer_account_id, payer_account);
+
+        let mut creator_account = self
+            .get_account(op.creator_account_id)
+            .ok_or(MintNFTOpError::CreatorAccountNotFound)?;
+        // Re-checked here (not just in `create_op`) so the op is self-consistent if `apply_op` is
+        // ever invoked directly against an already-built `ZkSyncOp` (e.g. block replay) without
+        // going through `create_op` again — this is the replay protection the authorization nonce
+        // exists for, so it must hold at the point the nonce is actually consumed.
+        let mint_auth_nonce = creator_account.get_balance(NFT_MINT_AUTH_NONCE_ID);
+        invariant!(
+            mint_auth_nonce == BigUint::from(op.tx.mint_auth_nonce.0),
+            MintNFTOpError::AuthorizationNonceMismatch
+        );
+
+        // Bump the creator's mint authorization nonce so the same pre-signed message cannot be
+        // replayed by a second submitter: the creator's regular on-chain nonce is never touched
+        // by this op, so it cannot serve as replay protection on its own.
+        let old_nonce_balance = creator_account.get_balance(NFT_MINT_AUTH_NONCE_ID);
+        creator_account.add_balance(NFT_MINT_AUTH_NONCE_ID, &BigUint::from(1u32));
+        let new_nonce_balance = creator_account.get_balance(NFT_MINT_AUTH_NONCE_ID);
+        updates.push((
+            op.creator_account_id,
+            AccountUpdate::UpdateBalance {
+                balance_update: (NFT_MINT_AUTH_NONCE_ID, old_nonce_balance, new_nonce_balance),
+                old_nonce: creator_account.nonce,
+                new_nonce: creator_account.nonce,
+            },
+        ));
+        self.insert_account(op.creator_account_id, creator_account.clone());
+
+        // The creator's serial-id counter still advances on every mint it authorizes (handled by
+        // `mint_nft_item`, shared with the other two mint paths), whether or not it is the one
+        // paying for it.
+        let mint_nonce = creator_account.nonce;
+        let (_token, item_updates) = self.mint_nft_item(
+            op.creator_account_id,
+            &mut creator_account,
+            mint_nonce,
+            op.recipient_account_id,
+            op.tx.content_hash,
+            op.tx.attributes.clone(),
+            // Pre-signed mints don't yet carry a royalty authorization; creators using this path
+            // get no royalty until it's threaded through `PreSignedMintNFT` in a future change.
+            0u16,
+        )?;
+        updates.extend(item_updates);
+
+        let fee = CollectedFee {
+            token: op.tx.fee_token,
+            amount: op.tx.fee.clone(),
+        };
+
+        metrics::histogram!("state.mint_nft", start.elapsed());
+        Ok((Some(fee), updates))
+    }
+}
+
+/// Mints several NFTs for one creator in a single transaction: one nonce increment and one fee
+/// settlement cover the whole batch, instead of every item paying for its own creator/storage
+/// account round-trips. Used for drop/enumerable mints where thousands of items are issued at once.
+impl TxHandler<MintNFTBatch> for ZkSyncState {
+    type Op = MintNFTBatchOp;
+    type OpError = MintNFTOpError;
+
+    fn create_op(&self, tx: MintNFTBatch) -> Result<Self::Op, Self::OpError> {
+        invariant!(
+            tx.fee_token <= max_processable_token(),
+            MintNFTOpError::InvalidTokenId
+        );
+        invariant!(
+            !tx.items.is_empty() && tx.items.len() <= MAX_MINT_NFT_BATCH_SIZE,
+            MintNFTOpError::BatchSizeExceeded
+        );
+        for item in &tx.items {
+            invariant!(
+                item.recipient != Address::zero(),
+                MintNFTOpError::RecipientAccountIncorrect
+            );
+            invariant!(
+                item.attributes.len() <= MAX_NFT_ATTRIBUTES,
+                MintNFTOpError::TooManyAttributes
+            );
+        }
+
+        let creator = self
+            .get_account(tx.creator_id)
+            .ok_or(MintNFTOpError::CreatorAccountNotFound)?;
+        invariant!(
+            creator.pub_key_hash != PubKeyHash::default(),
+            MintNFTOpError::CreatorAccountIsLocked
+        );
+
+        if let Some((pub_key_hash, _)) = tx.verify_signature() {
+            if pub_key_hash != creator.pub_key_hash {
+                return Err(MintNFTOpError::InvalidSignature);
+            }
+        }
+
+        let mut recipient_account_ids = Vec::with_capacity(tx.items.len());
+        for item in &tx.items {
+            let (recipient, _) = self
+                .get_account_by_address(&item.recipient)
+                .ok_or(MintNFTOpError::RecipientAccountNotFound)?;
+            recipient_account_ids.push(recipient);
+        }
+
+        let op = MintNFTBatchOp {
+            creator_account_id: tx.creator_id,
+            recipient_account_ids,
+            tx,
+        };
+
+        Ok(op)
+    }
+
+    fn apply_tx(&mut self, tx: MintNFTBatch) -> Result<OpSuccess, Self::OpError> {
+        let op = self.create_op(tx)?;
+
+        let (fee, updates) = <Self as TxHandler<MintNFTBatch>>::apply_op(self, &op)?;
+        let result = OpSuccess {
+            fee,
+            updates,
+            executed_op: ZkSyncOp::MintNFTBatchOp(Box::new(op)),
+        };
+
+        Ok(result)
+    }
+
+    fn apply_op(
+        &mut self,
+        op: &Self::Op,
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), Self::OpError> {
+        let start = Instant::now();
+        let mut updates = Vec::new();
+
+        // The creator pays a single fee for the whole batch, regardless of how many items it contains.
+        let mut creator_account = self
+            .get_account(op.creator_account_id)
+            .ok_or(MintNFTOpError::CreatorAccountNotFound)?;
+        let old_balance = creator_account.get_balance(op.tx.fee_token);
+        let nonce = creator_account.nonce;
+        invariant!(nonce == op.tx.nonce, MintNFTOpError::NonceMismatch);
+
+        invariant!(
+            old_balance >= op.tx.fee,
+            MintNFTOpError::InsufficientBalance
+        );
+        creator_account.sub_balance(op.tx.fee_token, &op.tx.fee);
+        let new_balance = creator_account.get_balance(op.tx.fee_token);
+        *creator_account.nonce += 1;
+        updates.push((
+            op.creator_account_id,
+            AccountUpdate::UpdateBalance {
+                balance_update: (op.tx.fee_token, old_balance, new_balance),
+                old_nonce: nonce,
+                new_nonce: creator_account.nonce,
+            },
+        ));
+        self.insert_account(op.creator_account_id, creator_account.clone());
+
+        // Every item still advances the creator's serial-id counter and the storage account's
+        // global token-id counter exactly once, the same as it would for a standalone mint.
+        for (item, &recipient_account_id) in op.tx.items.iter().zip(&op.recipient_account_ids) {
+            let (token, item_updates) = self.mint_nft_item(
+                op.creator_account_id,
+                &mut creator_account,
+                nonce,
+                recipient_account_id,
+                item.content_hash,
+                item.attributes.clone(),
+                // Batch items don't carry a per-item royalty authorization in this chunk.
+                0u16,
+            )?;
+            updates.extend(item_updates);
+            let _ = token;
+        }
+
+        let fee = CollectedFee {
+            token: op.tx.fee_token,
+            amount: op.tx.fee.clone(),
+        };
+
+        metrics::histogram!("state.mint_nft_batch", start.elapsed());
+        Ok((Some(fee), updates))
+    }
+}
+
+impl ZkSyncState {
+    /// Which NFTs `owner_account_id` currently owns, without scanning every account's balances.
+    pub fn nfts_owned_by(&self, owner_account_id: AccountId) -> HashSet<TokenId> {
+        self.owner_nft_index
+            .get(&owner_account_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The origin of `token_id`: its creator, the creator's serial id for it, and the mint block.
+    pub fn nft_provenance(&self, token_id: TokenId) -> Option<NFTProvenance> {
+        self.nft_provenance.get(&token_id).copied()
+    }
+
+    /// The royalty, in basis points, that `token_id`'s creator was configured to receive at mint
+    /// time. `Some(0)` if the token has no recorded royalty; `None` only if the NFT storage account
+    /// does not exist.
+    fn nft_royalty_bps(&self, token_id: TokenId) -> Option<u16> {
+        let royalty_slot = TokenId(token_id.0 + NFT_TOKEN_ROYALTY_ID_OFFSET);
+        let storage_account = self.get_account(NFT_STORAGE_ACCOUNT_ID)?;
+        storage_account.get_balance(royalty_slot).to_u16()
+    }
+
+    /// Computes the creator's cut of `price` (denominated in `fee_token`) when `token_id` changes
+    /// hands, so a transfer handler can credit it via `AccountUpdate::UpdateBalance`. Returns
+    /// `None` if the token carries no royalty, so callers can skip the credit entirely.
+    pub fn royalty_for(
+        &self,
+        token_id: TokenId,
+        price: &BigUint,
+        fee_token: TokenId,
+    ) -> Option<CollectedFee> {
+        let royalty_bps = self.nft_royalty_bps(token_id)?;
+        if royalty_bps == 0 {
+            return None;
+        }
+        let amount = price * BigUint::from(royalty_bps) / BigUint::from(10_000u32);
+        Some(CollectedFee {
+            token: fee_token,
+            amount,
+        })
+    }
+
+    /// Indexes `token_id` as owned by `owner_account_id` and records its provenance. Mint is the
+    /// only place a token's balance goes from zero to non-zero without an existing owner to move
+    /// it from, so this is called once per minted token rather than from a generic balance-update
+    /// hook.
+    fn index_nft_mint(
+        &mut self,
+        token_id: TokenId,
+        owner_account_id: AccountId,
+        creator_id: AccountId,
+        serial_id: u32,
+    ) {
+        self.owner_nft_index
+            .entry(owner_account_id)
+            .or_insert_with(HashSet::new)
+            .insert(token_id);
+        self.nft_provenance.insert(
+            token_id,
+            NFTProvenance {
+                creator_id,
+                serial_id,
+                mint_block: self.block_number,
+            },
+        );
+    }
+
+    /// Undoes [`Self::index_nft_mint`], so reverting a mint's `AccountUpdate`s leaves the index
+    /// consistent with the rolled-back state rather than pointing at a token that no longer exists.
+    ///
+    /// The generic `AccountUpdate` rollback/revert path lives in the core state-transition driver,
+    /// not in this handler module, and isn't part of this tree; whatever drives that rollback is
+    /// responsible for calling this once it undoes a mint's updates, or a reverted mint will leave
+    /// `owner_nft_index`/`nft_provenance` pointing at a token that no longer exists.
+    pub fn revert_nft_mint_index(&mut self, token_id: TokenId, owner_account_id: AccountId) {
+        if let Some(owned) = self.owner_nft_index.get_mut(&owner_account_id) {
+            owned.remove(&token_id);
+            if owned.is_empty() {
+                self.owner_nft_index.remove(&owner_account_id);
+            }
+        }
+        self.nft_provenance.remove(&token_id);
+    }
+
+    /// Moves `token_id`'s ownership-index entry from `from_account_id` to `to_account_id`.
+    /// Provenance (creator, serial id, mint block) is fixed at mint time and never changes hands,
+    /// so unlike [`Self::revert_nft_mint_index`] this leaves `nft_provenance` untouched.
+    ///
+    /// `mint_nft.rs` only knows how to index a token at mint time; there is no transfer handler
+    /// in this tree yet; whichever one lands is responsible for calling this once a transfer's
+    /// `AccountUpdate`s have moved the token's balance, or `nfts_owned_by` will keep reporting the
+    /// old owner after a real transfer.
+    pub fn transfer_nft_index(
+        &mut self,
+        token_id: TokenId,
+        from_account_id: AccountId,
+        to_account_id: AccountId,
+    ) {
+        if let Some(owned) = self.owner_nft_index.get_mut(&from_account_id) {
+            owned.remove(&token_id);
+            if owned.is_empty() {
+                self.owner_nft_index.remove(&from_account_id);
+            }
+        }
+        self.owner_nft_index
+            .entry(to_account_id)
+            .or_insert_with(HashSet::new)
+            .insert(token_id);
+    }
+
+    /// Mints a single token for `creator_account_id`: bumps the creator's serial-id counter and
+    /// the storage account's global token-id counter, computes the token hash/address/data, and
+    /// credits the recipient. Shared by [`MintNFTBatch`] so every item in a batch goes through
+    /// the exact same bookkeeping as a standalone mint, just without its own fee/nonce step.
+    fn mint_nft_item(
+        &mut self,
+        creator_account_id: AccountId,
+        creator_account: &mut Account,
+        mint_nonce: Nonce,
+        recipient_account_id: AccountId,
+        content_hash: H256,
+        attributes: Vec<NFTAttribute>,
+        royalty_bps: u16,
+    ) -> Result<(NFT, AccountUpdates), MintNFTOpError> {
+        let mut updates = Vec::new();
+
         let old_balance = creator_account.get_balance(NFT_TOKEN_ID);
         let old_nonce = creator_account.nonce;
         let serial_id = old_balance.to_u32().unwrap_or_default();
         creator_account.add_balance(NFT_TOKEN_ID, &BigUint::from(1u32));
         let new_balance = creator_account.get_balance(NFT_TOKEN_ID);
         updates.push((
-            op.creator_account_id,
+            creator_account_id,
             AccountUpdate::UpdateBalance {
                 balance_update: (NFT_TOKEN_ID, old_balance, new_balance),
                 old_nonce,
                 new_nonce: creator_account.nonce,
             },
         ));
-        self.insert_account(op.creator_account_id, creator_account.clone());
+        self.insert_account(creator_account_id, creator_account.clone());
 
-        // The address for the nft token is generated based on `creator_account_id`,` serial_id` and `content_hash`
-        // Generate token id. We have a special NFT account, which stores the next token id for nft in balance of NFT_TOKEN
         let (mut nft_account, account_updates) = self.get_or_create_nft_account_token_id();
         updates.extend(account_updates);
 
@@ -137,31 +645,41 @@ impl TxHandler<MintNFT> for ZkSyncState {
         ));
         self.insert_account(NFT_STORAGE_ACCOUNT_ID, nft_account.clone());
 
-        // Mint NFT with precalculated token_id, serial_id and address
         let token_id = TokenId(new_token_id.to_u32().expect("Should be correct u32"));
-        let token_hash = calculate_token_hash(op.tx.creator_id, serial_id, op.tx.content_hash);
+        // Staying below `NFT_TOKEN_ATTRIBUTES_ID_OFFSET` is what keeps the real-id/attribute/
+        // royalty ranges disjoint (see the module-level assertion above); the two `checked_add`s
+        // on top of that catch `token_id` values large enough to overflow `u32` once offset.
+        invariant!(
+            token_id.0 < NFT_TOKEN_ATTRIBUTES_ID_OFFSET
+                && token_id.0.checked_add(NFT_TOKEN_ATTRIBUTES_ID_OFFSET).is_some()
+                && token_id.0.checked_add(NFT_TOKEN_ROYALTY_ID_OFFSET).is_some(),
+            MintNFTOpError::TokenIdOverflow
+        );
+        let attributes_hash = calculate_token_attributes_hash(&attributes);
+        let token_hash =
+            calculate_token_hash(creator_account_id, serial_id, content_hash, &attributes_hash);
         let token_address = calculate_token_address(&token_hash);
         let token = NFT::new(
             token_id,
             serial_id,
-            op.tx.creator_id,
+            creator_account_id,
             creator_account.address,
             token_address,
             None,
-            op.tx.content_hash,
+            content_hash,
+            attributes,
+            royalty_bps,
         );
         updates.push((
-            op.creator_account_id,
+            creator_account_id,
             AccountUpdate::MintNFT {
                 token: token.clone(),
-                nonce,
+                nonce: mint_nonce,
             },
         ));
-        self.nfts.insert(token_id, token);
-        self.insert_account(op.creator_account_id, creator_account);
+        self.nfts.insert(token_id, token.clone());
+        self.insert_account(creator_account_id, creator_account.clone());
 
-        // Token data is a special balance for NFT_STORAGE_ACCOUNT,
-        // which represent last 16 bytes of hash of (account_id, serial_id, content_hash) for storing this data in circuit
         let token_data = calculate_token_data(&token_hash);
         let old_balance = nft_account.get_balance(token_id);
         assert_eq!(
@@ -178,11 +696,48 @@ impl TxHandler<MintNFT> for ZkSyncState {
                 new_nonce: nft_account.nonce,
             },
         ));
+
+        let attributes_slot = TokenId(token_id.0 + NFT_TOKEN_ATTRIBUTES_ID_OFFSET);
+        let old_attributes_balance = nft_account.get_balance(attributes_slot);
+        assert_eq!(
+            old_attributes_balance,
+            BigUint::zero(),
+            "The attributes balance of nft token must be zero"
+        );
+        nft_account.add_balance(attributes_slot, &attributes_hash);
+        updates.push((
+            NFT_STORAGE_ACCOUNT_ID,
+            AccountUpdate::UpdateBalance {
+                balance_update: (attributes_slot, BigUint::zero(), attributes_hash),
+                old_nonce: nft_account.nonce,
+                new_nonce: nft_account.nonce,
+            },
+        ));
+        // The royalty, like the attribute digest, is witnessed as its own reserved slot so a
+        // transfer can look it up (via `royalty_for`) without an off-chain index.
+        if royalty_bps > 0 {
+            let royalty_slot = TokenId(token_id.0 + NFT_TOKEN_ROYALTY_ID_OFFSET);
+            let old_royalty_balance = nft_account.get_balance(royalty_slot);
+            assert_eq!(
+                old_royalty_balance,
+                BigUint::zero(),
+                "The royalty balance of nft token must be zero"
+            );
+            let royalty_amount = BigUint::from(royalty_bps);
+            nft_account.add_balance(royalty_slot, &royalty_amount);
+            updates.push((
+                NFT_STORAGE_ACCOUNT_ID,
+                AccountUpdate::UpdateBalance {
+                    balance_update: (royalty_slot, BigUint::zero(), royalty_amount),
+                    old_nonce: nft_account.nonce,
+                    new_nonce: nft_account.nonce,
+                },
+            ));
+        }
         self.insert_account(NFT_STORAGE_ACCOUNT_ID, nft_account);
 
-        // Add this token to recipient account
         let mut recipient_account = self
-            .get_account(op.recipient_account_id)
+            .get_account(recipient_account_id)
             .ok_or(MintNFTOpError::RecipientAccountNotFound)?;
         let old_amount = recipient_account.get_balance(token_id);
         invariant!(
@@ -192,25 +747,19 @@ impl TxHandler<MintNFT> for ZkSyncState {
         let old_nonce = recipient_account.nonce;
         recipient_account.add_balance(token_id, &BigUint::from(1u32));
         updates.push((
-            op.recipient_account_id,
+            recipient_account_id,
             AccountUpdate::UpdateBalance {
                 balance_update: (token_id, BigUint::zero(), BigUint::from(1u32)),
                 old_nonce,
                 new_nonce: recipient_account.nonce,
             },
         ));
-        self.insert_account(op.recipient_account_id, recipient_account);
-
-        let fee = CollectedFee {
-            token: op.tx.fee_token,
-            amount: op.tx.fee.clone(),
-        };
+        self.insert_account(recipient_account_id, recipient_account);
+        self.index_nft_mint(token_id, recipient_account_id, creator_account_id, serial_id);
 
-        metrics::histogram!("state.mint_nft", start.elapsed());
-        Ok((Some(fee), updates))
+        Ok((token, updates))
     }
-}
-impl ZkSyncState {
+
     /// Get or create special account with special balance for enforcing uniqueness of token_id
     fn get_or_create_nft_account_token_id(&mut self) -> (Account, AccountUpdates) {
         let mut updates = vec![];
@@ -237,3 +786,106 @@ impl ZkSyncState {
         (account, updates)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn royalty_for_is_none_when_nothing_was_minted() {
+        let state = ZkSyncState::empty();
+        let price = BigUint::from(1_000u32);
+        assert!(state
+            .royalty_for(TokenId(MIN_NFT_TOKEN_ID), &price, TokenId(0))
+            .is_none());
+    }
+
+    #[test]
+    fn transfer_nft_index_moves_ownership_but_keeps_provenance() {
+        let mut state = ZkSyncState::empty();
+        let token_id = TokenId(MIN_NFT_TOKEN_ID);
+        let creator_id = AccountId(1);
+        let old_owner = AccountId(2);
+        let new_owner = AccountId(3);
+
+        state.index_nft_mint(token_id, old_owner, creator_id, 0);
+        assert!(state.nfts_owned_by(old_owner).contains(&token_id));
+        assert!(!state.nfts_owned_by(new_owner).contains(&token_id));
+
+        state.transfer_nft_index(token_id, old_owner, new_owner);
+
+        assert!(!state.nfts_owned_by(old_owner).contains(&token_id));
+        assert!(state.nfts_owned_by(new_owner).contains(&token_id));
+        assert_eq!(
+            state.nft_provenance(token_id).unwrap().creator_id,
+            creator_id
+        );
+    }
+
+    #[test]
+    fn revert_nft_mint_index_undoes_index_nft_mint() {
+        let mut state = ZkSyncState::empty();
+        let token_id = TokenId(MIN_NFT_TOKEN_ID);
+        let creator_id = AccountId(1);
+        let owner = AccountId(2);
+
+        state.index_nft_mint(token_id, owner, creator_id, 0);
+        state.revert_nft_mint_index(token_id, owner);
+
+        assert!(!state.nfts_owned_by(owner).contains(&token_id));
+        assert!(state.nft_provenance(token_id).is_none());
+    }
+
+    #[test]
+    fn mint_nft_item_rejects_token_id_that_would_collide_with_its_own_slots() {
+        let mut state = ZkSyncState::empty();
+
+        let (mut nft_account, _) =
+            Account::create_account(NFT_STORAGE_ACCOUNT_ID, *NFT_STORAGE_ACCOUNT_ADDRESS);
+        nft_account.add_balance(
+            NFT_TOKEN_ID,
+            &BigUint::from(NFT_TOKEN_ATTRIBUTES_ID_OFFSET),
+        );
+        state.insert_account(NFT_STORAGE_ACCOUNT_ID, nft_account);
+
+        let (mut creator_account, _) = Account::create_account(AccountId(1), Address::zero());
+        let result = state.mint_nft_item(
+            AccountId(1),
+            &mut creator_account,
+            Nonce(0),
+            AccountId(2),
+            H256::zero(),
+            vec![],
+            0u16,
+        );
+
+        assert!(matches!(result, Err(MintNFTOpError::TokenIdOverflow)));
+    }
+
+    #[test]
+    fn pre_signed_mint_nft_rejects_fee_above_max_fee() {
+        let state = ZkSyncState::empty();
+        let tx = PreSignedMintNFT {
+            fee: BigUint::from(10u32),
+            max_fee: BigUint::from(1u32),
+            ..Default::default()
+        };
+
+        let result = state.create_op(tx);
+
+        assert!(matches!(result, Err(MintNFTOpError::FeeExceedsMaxFee)));
+    }
+
+    #[test]
+    fn mint_nft_batch_rejects_empty_batch() {
+        let state = ZkSyncState::empty();
+        let tx = MintNFTBatch {
+            items: vec![],
+            ..Default::default()
+        };
+
+        let result = state.create_op(tx);
+
+        assert!(matches!(result, Err(MintNFTOpError::BatchSizeExceeded)));
+    }
+}